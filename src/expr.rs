@@ -0,0 +1,332 @@
+//! Runtime expression parser for custom integrands.
+//!
+//! Converts a string such as `sin(x)/x + x^2` into a `Box<dyn Fn(f64) -> f64>`
+//! via the classic two-pass approach: tokenize, run shunting-yard to produce
+//! an RPN token stream, then fold the RPN into an [`Expr`] AST that can be
+//! evaluated repeatedly without re-parsing the source string.
+//!
+//! Supported grammar: `+ - * / ^`, unary minus, parentheses, the variable
+//! `x`, and single-argument calls to `sin`, `cos`, `sqrt`, `exp`, `ln`.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Func {
+    Sin,
+    Cos,
+    Sqrt,
+    Exp,
+    Ln,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(f64),
+    Var,
+    Op(Op),
+    Func(Func),
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    Var,
+    Neg(Box<Expr>),
+    BinOp(Op, Box<Expr>, Box<Expr>),
+    Call(Func, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, x: f64) -> f64 {
+        match self {
+            Expr::Num(value) => *value,
+            Expr::Var => x,
+            Expr::Neg(inner) => -inner.eval(x),
+            Expr::BinOp(op, lhs, rhs) => {
+                let lhs = lhs.eval(x);
+                let rhs = rhs.eval(x);
+                match op {
+                    Op::Add => lhs + rhs,
+                    Op::Sub => lhs - rhs,
+                    Op::Mul => lhs * rhs,
+                    Op::Div => lhs / rhs,
+                    Op::Pow => lhs.powf(rhs),
+                }
+            }
+            Expr::Call(func, arg) => {
+                let arg = arg.eval(x);
+                match func {
+                    Func::Sin => arg.sin(),
+                    Func::Cos => arg.cos(),
+                    Func::Sqrt => arg.sqrt(),
+                    Func::Exp => arg.exp(),
+                    Func::Ln => arg.ln(),
+                }
+            }
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| ParseError(format!("invalid number '{text}'")))?;
+            tokens.push(Token::Number(value));
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(match text.as_str() {
+                "x" => Token::Var,
+                "sin" => Token::Func(Func::Sin),
+                "cos" => Token::Func(Func::Cos),
+                "sqrt" => Token::Func(Func::Sqrt),
+                "exp" => Token::Func(Func::Exp),
+                "ln" => Token::Func(Func::Ln),
+                other => return Err(ParseError(format!("unknown identifier '{other}'"))),
+            });
+            continue;
+        }
+
+        let op = match c {
+            '+' => Some(Op::Add),
+            '-' => Some(Op::Sub),
+            '*' => Some(Op::Mul),
+            '/' => Some(Op::Div),
+            '^' => Some(Op::Pow),
+            _ => None,
+        };
+
+        if let Some(op) = op {
+            tokens.push(Token::Op(op));
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            other => return Err(ParseError(format!("unexpected character '{other}'"))),
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+fn precedence(op: Op) -> u8 {
+    match op {
+        Op::Add | Op::Sub => 1,
+        Op::Mul | Op::Div => 2,
+        Op::Pow => 3,
+    }
+}
+
+fn is_right_associative(op: Op) -> bool {
+    matches!(op, Op::Pow)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RpnItem {
+    Number(f64),
+    Var,
+    Neg,
+    BinOp(Op),
+    Call(Func),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StackOp {
+    Binary(Op),
+    Unary,
+    Func(Func),
+    LParen,
+}
+
+/// Shunting-yard: infix tokens -> RPN. Unary minus is recognised whenever a
+/// `-` does not follow something that could end an expression (a number,
+/// `x`, or a closing paren); it binds tighter than `+ - * /` but looser
+/// than `^`.
+fn to_rpn(tokens: &[Token]) -> Result<Vec<RpnItem>, ParseError> {
+    let mut output = Vec::new();
+    let mut ops: Vec<StackOp> = Vec::new();
+    let mut prev_operand = false;
+
+    for &token in tokens {
+        match token {
+            Token::Number(value) => {
+                output.push(RpnItem::Number(value));
+                prev_operand = true;
+            }
+            Token::Var => {
+                output.push(RpnItem::Var);
+                prev_operand = true;
+            }
+            Token::Func(func) => {
+                ops.push(StackOp::Func(func));
+                prev_operand = false;
+            }
+            Token::LParen => {
+                ops.push(StackOp::LParen);
+                prev_operand = false;
+            }
+            Token::RParen => {
+                loop {
+                    match ops.pop() {
+                        Some(StackOp::LParen) => break,
+                        Some(op) => output.push(stack_op_to_rpn(op)),
+                        None => return Err(ParseError("mismatched parentheses".into())),
+                    }
+                }
+                if let Some(StackOp::Func(func)) = ops.last().copied() {
+                    ops.pop();
+                    output.push(RpnItem::Call(func));
+                }
+                prev_operand = true;
+            }
+            Token::Op(Op::Sub) if !prev_operand => {
+                ops.push(StackOp::Unary);
+                prev_operand = false;
+            }
+            Token::Op(op) => {
+                while let Some(top) = ops.last().copied() {
+                    let should_pop = match top {
+                        StackOp::Binary(top_op) => {
+                            precedence(top_op) > precedence(op)
+                                || (precedence(top_op) == precedence(op) && !is_right_associative(op))
+                        }
+                        // Unary minus binds tighter than +-*/ but looser than
+                        // `^`, so `-x^2` parses as `-(x^2)`, matching the
+                        // usual calculator convention.
+                        StackOp::Unary => op != Op::Pow,
+                        StackOp::Func(_) => true,
+                        StackOp::LParen => false,
+                    };
+                    if !should_pop {
+                        break;
+                    }
+                    ops.pop();
+                    output.push(stack_op_to_rpn(top));
+                }
+                ops.push(StackOp::Binary(op));
+                prev_operand = false;
+            }
+        }
+    }
+
+    while let Some(op) = ops.pop() {
+        if matches!(op, StackOp::LParen) {
+            return Err(ParseError("mismatched parentheses".into()));
+        }
+        output.push(stack_op_to_rpn(op));
+    }
+
+    Ok(output)
+}
+
+fn stack_op_to_rpn(op: StackOp) -> RpnItem {
+    match op {
+        StackOp::Binary(op) => RpnItem::BinOp(op),
+        StackOp::Unary => RpnItem::Neg,
+        StackOp::Func(func) => RpnItem::Call(func),
+        StackOp::LParen => unreachable!("left paren never reaches the RPN output"),
+    }
+}
+
+fn build_ast(rpn: &[RpnItem]) -> Result<Expr, ParseError> {
+    let mut stack: Vec<Expr> = Vec::new();
+
+    for &item in rpn {
+        let expr = match item {
+            RpnItem::Number(value) => Expr::Num(value),
+            RpnItem::Var => Expr::Var,
+            RpnItem::Neg => {
+                let inner = stack
+                    .pop()
+                    .ok_or_else(|| ParseError("malformed expression".into()))?;
+                Expr::Neg(Box::new(inner))
+            }
+            RpnItem::BinOp(op) => {
+                let rhs = stack
+                    .pop()
+                    .ok_or_else(|| ParseError("malformed expression".into()))?;
+                let lhs = stack
+                    .pop()
+                    .ok_or_else(|| ParseError("malformed expression".into()))?;
+                Expr::BinOp(op, Box::new(lhs), Box::new(rhs))
+            }
+            RpnItem::Call(func) => {
+                let arg = stack
+                    .pop()
+                    .ok_or_else(|| ParseError("malformed expression".into()))?;
+                Expr::Call(func, Box::new(arg))
+            }
+        };
+        stack.push(expr);
+    }
+
+    match stack.len() {
+        1 => Ok(stack.pop().unwrap()),
+        0 => Err(ParseError("empty expression".into())),
+        _ => Err(ParseError("malformed expression".into())),
+    }
+}
+
+/// Parse `input` as a function of `x` and return a boxed closure that
+/// evaluates it. The AST is built once up front, so calling the returned
+/// closure repeatedly (as the quadrature methods do) doesn't re-parse.
+pub fn parse_expression(input: &str) -> Result<Box<dyn Fn(f64) -> f64>, ParseError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(ParseError("empty expression".into()));
+    }
+    let rpn = to_rpn(&tokens)?;
+    let ast = build_ast(&rpn)?;
+
+    Ok(Box::new(move |x| ast.eval(x)))
+}
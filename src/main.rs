@@ -1,14 +1,25 @@
 use core::panic;
+use std::fmt;
 use std::ops::{Range, RangeBounds};
 
 use inquire::{
     list_option::ListOption,
     validator::{ErrorMessage, Validation},
-    CustomType, InquireError, Select,
+    CustomType, InquireError, Select, Text,
 };
 
+mod expr;
+mod tabulated;
+
 type TNumber = f64;
 const MAX_ITERATIONS: usize = 100_000;
+const MAX_ADAPTIVE_SIMPSON_DEPTH: usize = 50;
+const MAX_ROMBERG_ROWS: usize = 30;
+const ERROR_BOUND_SAMPLE_POINTS: usize = 200;
+const ERROR_BOUND_DERIVATIVE_DELTA: f64 = 1e-3;
+const GAUSS_LEGENDRE_MIN_ORDER: usize = 2;
+const GAUSS_LEGENDRE_MAX_ORDER: usize = 5;
+const TABULATED_WIDTH_EPSILON: f64 = 1e-9;
 
 #[derive(Clone, Copy)]
 enum RectangleMode {
@@ -22,23 +33,123 @@ enum ComputationMethod {
     Rectangle(RectangleMode),
     Trapezoid,
     Sympthonm,
+    AdaptiveSimpson,
+    GaussLegendre(usize),
+    Romberg,
+}
+
+/// Bounds the arithmetic and transcendental operations the quadrature core
+/// (`Config`, `compute_integral`, the rectangle/trapezoid/Simpson solvers,
+/// `compute_step_length`, `runge_rule`) needs, so they can run at either
+/// `f32` or `f64` precision. Conversion to/from `f64` is via dedicated
+/// methods rather than `std::convert::From<f64>`, since that conversion is
+/// lossy for `f32` and the standard trait can't express it.
+trait Scalar:
+    Copy
+    + PartialOrd
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::ops::Div<Output = Self>
+{
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn zero() -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn abs(self) -> Self;
+}
+
+impl Scalar for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn zero() -> Self {
+        0.
+    }
+
+    fn powi(self, n: i32) -> Self {
+        f64::powi(self, n)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+}
+
+impl Scalar for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn zero() -> Self {
+        0.
+    }
+
+    fn powi(self, n: i32) -> Self {
+        f32::powi(self, n)
+    }
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
 }
 
 #[derive(Clone)]
-struct Config {
-    range: Range<TNumber>,
+struct Config<S: Scalar = TNumber> {
+    range: Range<S>,
     number_of_splits: usize,
 }
 
-type SingleVariableFunction = fn(f64) -> f64;
+/// Carries a fixed, possibly non-equidistant, list of `(x, y)` samples —
+/// the tabulated-data counterpart of [`Config`], which instead describes a
+/// closed-form function over a `Range` with a chosen split count.
+#[derive(Clone)]
+struct TabulatedConfig {
+    points: Vec<(TNumber, TNumber)>,
+}
 
-const FUNCTIONS: [SingleVariableFunction; 4] = [
+type SingleVariableFunction = Box<dyn Fn(f64) -> f64>;
+
+type BuiltinFunction = fn(f64) -> f64;
+
+const FUNCTIONS: [BuiltinFunction; 4] = [
     |x| 2. * x.powi(3) - 2. * x.powi(2) + 7. * x - 14.,
     |x| -3. * x.powi(3) - 5. * x.powi(2) + 4. * x - 2.,
     |x| x.sin() + 1.125,
     |x| x.sqrt().sin() + 2.,
 ];
 
+/// Raised when any rule run by the split-doubling loop produces a
+/// NaN/infinite result, which almost always means the integrand is singular
+/// somewhere on the interval.
+#[derive(Debug)]
+struct SingularIntegrandError {
+    start: f64,
+    end: f64,
+}
+
+impl fmt::Display for SingularIntegrandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The integrand produced a non-finite value on [{}, {}]; it is likely singular on this interval. \
+             Try an open rule that never samples the boundary, such as the midpoint rectangle or Gauss-Legendre.",
+            self.start, self.end
+        )
+    }
+}
+
+impl std::error::Error for SingularIntegrandError {}
+
 fn main() {
     match start() {
         Ok(_) => (),
@@ -47,6 +158,46 @@ fn main() {
 }
 
 fn start() -> Result<(), InquireError> {
+    let input_mode = Select::new(
+        "Select input mode",
+        vec!["Analytic function", "Tabulated samples"],
+    )
+    .raw_prompt()?
+    .index;
+
+    match input_mode {
+        0 => start_analytic(),
+        1 => start_tabulated(),
+        _ => panic!("Unsupported input mode with index {input_mode}"),
+    }
+}
+
+fn start_tabulated() -> Result<(), InquireError> {
+    let method_index = Select::new("Select method", vec!["Trapezoid", "Sympthon"])
+        .raw_prompt()?
+        .index;
+
+    let points = Text::new("Enter sample points as 'x,y' pairs separated by ';' (e.g. 0,1; 1,4; 2,9)")
+        .with_validator(|input: &str| match tabulated::parse_points(input) {
+            Ok(_) => Ok(Validation::Valid),
+            Err(error) => Ok(Validation::Invalid(ErrorMessage::Custom(error.to_string()))),
+        })
+        .prompt()?;
+    let points = tabulated::parse_points(&points).expect("validated by the prompt above");
+    let config = TabulatedConfig { points };
+
+    let integral = match method_index {
+        0 => solve_trapezoid_tabulated(&config),
+        1 => solve_simpson_tabulated(&config)
+            .map_err(|error| InquireError::Custom(error.into()))?,
+        _ => panic!("Unsupported method with index {method_index}"),
+    };
+
+    println!("Integral: {integral}");
+    Ok(())
+}
+
+fn start_analytic() -> Result<(), InquireError> {
     let function_index = Select::new(
         "Select function",
         vec![
@@ -54,6 +205,7 @@ fn start() -> Result<(), InquireError> {
             "-3x^3 - 5x^2 + 4x - 2",
             "sin(x) + 1.125",
             "sin(sqrt(x)) + 2",
+            "Custom expression",
         ],
     )
     .raw_prompt()?
@@ -62,7 +214,14 @@ fn start() -> Result<(), InquireError> {
     let method: ComputationMethod = {
         let index = Select::new(
             "Select method",
-            vec!["Rectangle (Central, right, left)", "Trapezoid", "Sympthon"],
+            vec![
+                "Rectangle (Central, right, left)",
+                "Trapezoid",
+                "Sympthon",
+                "Adaptive Simpson",
+                "Gauss-Legendre",
+                "Romberg",
+            ],
         )
         .raw_prompt()?
         .index;
@@ -88,6 +247,24 @@ fn start() -> Result<(), InquireError> {
             }
             1 => ComputationMethod::Trapezoid,
             2 => ComputationMethod::Sympthonm,
+            3 => ComputationMethod::AdaptiveSimpson,
+            4 => {
+                let order = CustomType::<usize>::new("Gauss-Legendre order (2-5)")
+                    .with_default(GAUSS_LEGENDRE_MIN_ORDER)
+                    .with_validator(|&value: &usize| {
+                        if (GAUSS_LEGENDRE_MIN_ORDER..=GAUSS_LEGENDRE_MAX_ORDER).contains(&value) {
+                            Ok(Validation::Valid)
+                        } else {
+                            Ok(Validation::Invalid(ErrorMessage::Custom(format!(
+                                "Order should be between {GAUSS_LEGENDRE_MIN_ORDER} and {GAUSS_LEGENDRE_MAX_ORDER}! Got {value}"
+                            ))))
+                        }
+                    })
+                    .prompt()?;
+
+                ComputationMethod::GaussLegendre(order)
+            }
+            5 => ComputationMethod::Romberg,
             _ => panic!("Unsupported method with index {index} method"),
         }
     };
@@ -115,7 +292,58 @@ fn start() -> Result<(), InquireError> {
         })
         .prompt()?;
 
-    let mut number_of_splits = CustomType::<usize>::new("Initial number of splits")
+    let function: SingleVariableFunction = if function_index == FUNCTIONS.len() {
+        let expression = Text::new("Enter expression in terms of x")
+            .with_validator(|input: &str| match expr::parse_expression(input) {
+                Ok(_) => Ok(Validation::Valid),
+                Err(error) => Ok(Validation::Invalid(ErrorMessage::Custom(error.to_string()))),
+            })
+            .prompt()?;
+
+        expr::parse_expression(&expression).expect("validated by the prompt above")
+    } else {
+        Box::new(FUNCTIONS[function_index])
+    };
+
+    if matches!(method, ComputationMethod::AdaptiveSimpson) {
+        let (integral, evaluations) = solve_adaptive_simpson(&range, epsilon, &function)
+            .map_err(|error| InquireError::Custom(error.into()))?;
+
+        println!("Integral: {integral}");
+        println!("Function evaluations used: {evaluations}");
+        return Ok(());
+    }
+
+    if matches!(method, ComputationMethod::Romberg) {
+        let (integral, rows_used) = solve_romberg(&range, epsilon, &function)
+            .map_err(|error| InquireError::Custom(error.into()))?;
+
+        println!("Integral: {integral}");
+        println!("Romberg rows used: {rows_used}");
+        return Ok(());
+    }
+
+    let method = {
+        let start_value = function(range.start);
+        let end_value = function(range.end);
+
+        if start_value.is_finite() && end_value.is_finite() {
+            method
+        } else {
+            match method {
+                ComputationMethod::Trapezoid | ComputationMethod::Sympthonm => {
+                    println!(
+                        "f({}) = {start_value}, f({}) = {end_value}: the integrand is non-finite at an endpoint, switching to the midpoint rectangle rule, which never samples the boundary.",
+                        range.start, range.end
+                    );
+                    ComputationMethod::Rectangle(RectangleMode::Center)
+                }
+                other => other,
+            }
+        }
+    };
+
+    let number_of_splits = CustomType::<usize>::new("Initial number of splits")
         .with_default(5)
         .with_validator(move |&value: &usize| {
             if matches!(method, ComputationMethod::Sympthonm) && value % 2 == 1 {
@@ -128,19 +356,64 @@ fn start() -> Result<(), InquireError> {
         })
         .prompt()?;
 
-    let function = FUNCTIONS[function_index];
+    let precision_index = Select::new("Select precision", vec!["f64", "f32"])
+        .raw_prompt()?
+        .index;
+
+    match precision_index {
+        0 => run_convergence_loop::<f64>(range, epsilon, method, &function, number_of_splits),
+        1 => run_convergence_loop::<f32>(range, epsilon, method, &function, number_of_splits),
+        _ => panic!("Unsupported precision with index {precision_index}"),
+    }
+}
+
+/// Runs the split-doubling convergence loop (shared by Rectangle, Trapezoid,
+/// Sympthon and Gauss-Legendre) at whichever precision `S` the user picked.
+/// Values pass through `f64` at each function evaluation, when printing,
+/// when reporting the non-finite error, and when handing the range to
+/// [`compute_error_bound`], which remains `f64`-only; Gauss-Legendre is
+/// computed wholly in `f64` and only its result is converted to `S`.
+fn run_convergence_loop<S: Scalar>(
+    range: Range<f64>,
+    epsilon: f64,
+    method: ComputationMethod,
+    function: &SingleVariableFunction,
+    mut number_of_splits: usize,
+) -> Result<(), InquireError> {
+    let range = S::from_f64(range.start)..S::from_f64(range.end);
+    let epsilon = S::from_f64(epsilon);
 
     for _ in 0..MAX_ITERATIONS {
         let integral = compute_integral(&range, number_of_splits, method, function);
         let number_of_splits_doubled = number_of_splits * 2;
         let double_splitted = compute_integral(&range, number_of_splits_doubled, method, function);
 
+        if !integral.to_f64().is_finite() || !double_splitted.to_f64().is_finite() {
+            return Err(InquireError::Custom(Box::new(SingularIntegrandError {
+                start: range.start.to_f64(),
+                end: range.end.to_f64(),
+            })));
+        }
+
         // deviation/error
         let divergence = runge_rule(double_splitted, integral, method);
 
         if divergence < epsilon {
+            let double_splitted = double_splitted.to_f64();
             println!("Integral: {double_splitted}");
             println!("Numebr of splits used: {number_of_splits_doubled}");
+            println!("Runge error estimate: {}", divergence.to_f64());
+
+            let final_config = Config {
+                range: range.start.to_f64()..range.end.to_f64(),
+                number_of_splits: number_of_splits_doubled,
+            };
+            if let Some(error_bound) =
+                compute_error_bound(&final_config, method, function).filter(|bound| bound.is_finite())
+            {
+                println!("Theoretical error bound: {error_bound}");
+            }
+
             return Ok(());
         }
 
@@ -155,12 +428,12 @@ Try to specify higher initial number of splits or lower precision"
     );
 }
 
-fn compute_integral(
-    range: &Range<f64>,
+fn compute_integral<S: Scalar>(
+    range: &Range<S>,
     number_of_splits: usize,
     method: ComputationMethod,
-    function: SingleVariableFunction,
-) -> f64 {
+    function: &SingleVariableFunction,
+) -> S {
     let config = Config {
         range: range.clone(),
         number_of_splits,
@@ -169,106 +442,461 @@ fn compute_integral(
         ComputationMethod::Rectangle(mode) => solve_rectanlge(&config, mode, function),
         ComputationMethod::Trapezoid => solve_trapezoid(&config, function),
         ComputationMethod::Sympthonm => solve_simpthon(&config, function),
+        ComputationMethod::GaussLegendre(order) => {
+            let config = Config {
+                range: S::to_f64(config.range.start)..S::to_f64(config.range.end),
+                number_of_splits: config.number_of_splits,
+            };
+            S::from_f64(solve_gauss_legendre(&config, order, function))
+        }
+        ComputationMethod::AdaptiveSimpson => {
+            unreachable!("adaptive Simpson bypasses the split-doubling loop entirely")
+        }
+        ComputationMethod::Romberg => {
+            unreachable!("Romberg bypasses the split-doubling loop entirely")
+        }
     }
 }
 
-fn solve_rectanlge(config: &Config, mode: RectangleMode, function: SingleVariableFunction) -> f64 {
-    let step_length = compute_step_length(&config);
+/// Evaluates `function` (always f64-valued) at the `S`-precision point `x`,
+/// converting through `f64` at the call boundary since `SingleVariableFunction`
+/// isn't itself generic over `S`.
+fn eval_at<S: Scalar>(function: &SingleVariableFunction, x: S) -> S {
+    S::from_f64(function(S::to_f64(x)))
+}
 
-    let half_step = step_length / 2.;
+fn solve_rectanlge<S: Scalar>(
+    config: &Config<S>,
+    mode: RectangleMode,
+    function: &SingleVariableFunction,
+) -> S {
+    let step_length = compute_step_length(config);
 
-    let mut integral_sum: f64 = 0.;
+    let half_step = step_length / S::from_f64(2.);
+
+    let mut integral_sum = S::zero();
 
     for left_border_multiplier in 0..config.number_of_splits {
-        let left_bound = config.range.start + step_length * left_border_multiplier as f64;
+        let left_bound = config.range.start + step_length * S::from_f64(left_border_multiplier as f64);
 
-        let height: f64 = match mode {
-            RectangleMode::Left => function(left_bound),
+        let height: S = match mode {
+            RectangleMode::Left => eval_at(function, left_bound),
             RectangleMode::Center => {
                 let center = left_bound + half_step;
-                function(center)
+                eval_at(function, center)
             }
             RectangleMode::Right => {
                 let right_bound = left_bound + step_length;
-                function(right_bound)
+                eval_at(function, right_bound)
             }
         };
 
         let area = height * step_length;
-        integral_sum += area;
+        integral_sum = integral_sum + area;
     }
 
     integral_sum
 }
 
-fn solve_trapezoid(config: &Config, function: SingleVariableFunction) -> f64 {
-    let step_length = compute_step_length(&config);
+fn solve_trapezoid<S: Scalar>(config: &Config<S>, function: &SingleVariableFunction) -> S {
+    let step_length = compute_step_length(config);
 
-    let first_point = function(config.range.start);
-    let end_point = function(config.range.end);
-    let mut sum_of_intermediate_points: f64 = 0.;
+    let first_point = eval_at(function, config.range.start);
+    let end_point = eval_at(function, config.range.end);
+    let mut sum_of_intermediate_points = S::zero();
 
     for point_index in 1..config.number_of_splits {
-        let point_x = config.range.start + step_length * point_index as f64;
-        let height = function(point_x);
-        sum_of_intermediate_points += height;
+        let point_x = config.range.start + step_length * S::from_f64(point_index as f64);
+        let height = eval_at(function, point_x);
+        sum_of_intermediate_points = sum_of_intermediate_points + height;
     }
 
-    step_length / 2. * (first_point + end_point + 2. * sum_of_intermediate_points)
+    step_length / S::from_f64(2.)
+        * (first_point + end_point + S::from_f64(2.) * sum_of_intermediate_points)
 }
 
-fn solve_simpthon(config: &Config, function: SingleVariableFunction) -> f64 {
+fn solve_simpthon<S: Scalar>(config: &Config<S>, function: &SingleVariableFunction) -> S {
     if config.number_of_splits % 2 != 0 {
         panic!("number of splits should be even");
     }
 
-    let step_length = compute_step_length(&config);
-    let first_point = function(config.range.start);
-    let end_point = function(config.range.end);
+    let step_length = compute_step_length(config);
+    let first_point = eval_at(function, config.range.start);
+    let end_point = eval_at(function, config.range.end);
 
-    let mut odd_sum: f64 = 0.;
-    let mut even_sum: f64 = 0.;
+    let mut odd_sum = S::zero();
+    let mut even_sum = S::zero();
 
     for index in 1..config.number_of_splits {
-        let point_x = config.range.start + step_length * index as f64;
-        let height = function(point_x);
+        let point_x = config.range.start + step_length * S::from_f64(index as f64);
+        let height = eval_at(function, point_x);
 
         if index % 2 == 0 {
-            even_sum += height;
+            even_sum = even_sum + height;
         } else {
-            odd_sum += height;
+            odd_sum = odd_sum + height;
+        }
+    }
+
+    step_length / S::from_f64(3.)
+        * (first_point + S::from_f64(4.) * odd_sum + S::from_f64(2.) * even_sum + end_point)
+}
+
+/// Composite Gauss-Legendre quadrature: `config.number_of_splits` equal
+/// subintervals, each integrated with the fixed `order`-point node/weight
+/// table mapped from `[-1, 1]` onto that subinterval.
+fn solve_gauss_legendre(config: &Config, order: usize, function: &SingleVariableFunction) -> f64 {
+    let step_length = compute_step_length(&config);
+    let nodes_and_weights = gauss_legendre_nodes_and_weights(order);
+
+    let mut integral_sum: f64 = 0.;
+
+    for split_index in 0..config.number_of_splits {
+        let left = config.range.start + step_length * split_index as f64;
+        let right = left + step_length;
+        let midpoint = (left + right) / 2.;
+        let half_width = (right - left) / 2.;
+
+        for &(node, weight) in nodes_and_weights {
+            let x = midpoint + half_width * node;
+            integral_sum += half_width * weight * function(x);
+        }
+    }
+
+    integral_sum
+}
+
+/// Standard Gauss-Legendre nodes/weights on `[-1, 1]` for `order` in `2..=5`.
+fn gauss_legendre_nodes_and_weights(order: usize) -> &'static [(f64, f64)] {
+    match order {
+        2 => &[
+            (-0.5773502691896257, 1.0),
+            (0.5773502691896257, 1.0),
+        ],
+        3 => &[
+            (-0.7745966692414834, 5. / 9.),
+            (0., 8. / 9.),
+            (0.7745966692414834, 5. / 9.),
+        ],
+        4 => &[
+            (-0.8611363115940526, 0.3478548451374538),
+            (-0.3399810435848563, 0.6521451548625461),
+            (0.3399810435848563, 0.6521451548625461),
+            (0.8611363115940526, 0.3478548451374538),
+        ],
+        5 => &[
+            (-0.906179845938664, 0.2369268850561891),
+            (-0.5384693101056831, 0.4786286704993665),
+            (0., 0.5688888888888889),
+            (0.5384693101056831, 0.4786286704993665),
+            (0.906179845938664, 0.2369268850561891),
+        ],
+        _ => panic!("Gauss-Legendre order should be between {GAUSS_LEGENDRE_MIN_ORDER} and {GAUSS_LEGENDRE_MAX_ORDER}! Got {order}"),
+    }
+}
+
+/// Composite trapezoid rule over possibly non-equidistant samples.
+fn solve_trapezoid_tabulated(config: &TabulatedConfig) -> f64 {
+    config
+        .points
+        .windows(2)
+        .map(|window| {
+            let (x0, y0) = window[0];
+            let (x1, y1) = window[1];
+            (x1 - x0) * (y0 + y1) / 2.
+        })
+        .sum()
+}
+
+/// Simpson's rule over consecutive windows of three points, each requiring
+/// equal spacing between the two sub-intervals. An odd number of intervals
+/// leaves one trailing sub-interval, which is integrated with the trapezoid
+/// rule instead.
+fn solve_simpson_tabulated(config: &TabulatedConfig) -> Result<f64, String> {
+    let points = &config.points;
+    let mut integral = 0.;
+    let mut index = 0;
+
+    while index + 2 < points.len() {
+        let (x0, y0) = points[index];
+        let (x1, y1) = points[index + 1];
+        let (x2, y2) = points[index + 2];
+
+        let left_width = x1 - x0;
+        let right_width = x2 - x1;
+
+        if (left_width - right_width).abs() > TABULATED_WIDTH_EPSILON {
+            return Err(format!(
+                "Simpson's rule needs equal-width intervals around x={x1}, got {left_width} and {right_width}"
+            ));
         }
+
+        integral += left_width / 3. * (y0 + 4. * y1 + y2);
+        index += 2;
     }
 
-    step_length / 3. * (first_point + 4. * odd_sum + 2. * even_sum + end_point)
+    if index + 1 < points.len() {
+        let (x0, y0) = points[index];
+        let (x1, y1) = points[index + 1];
+        integral += (x1 - x0) * (y0 + y1) / 2.;
+    }
+
+    Ok(integral)
 }
 
-fn compute_step_length(
+fn compute_step_length<S: Scalar>(
     Config {
         range,
         number_of_splits,
         ..
-    }: &Config,
-) -> f64 {
+    }: &Config<S>,
+) -> S {
     let range_length = range.len();
-    range_length / *number_of_splits as f64
+    range_length / S::from_f64(*number_of_splits as f64)
 }
 
-trait FloatRangeLength: RangeBounds<f64> {
-    fn len(&self) -> f64;
+trait ScalarRangeLength<S: Scalar>: RangeBounds<S> {
+    fn len(&self) -> S;
 }
 
-impl FloatRangeLength for Range<f64> {
-    fn len(&self) -> f64 {
+impl<S: Scalar> ScalarRangeLength<S> for Range<S> {
+    fn len(&self) -> S {
         self.end - self.start
     }
 }
 
-fn runge_rule(half: f64, full: f64, method: ComputationMethod) -> f64 {
+/// Adaptive Simpson quadrature: only recurses into subintervals where the
+/// Richardson-corrected estimate doesn't already agree with the coarse one,
+/// so smooth regions get far fewer evaluations than steep ones.
+fn solve_adaptive_simpson(
+    range: &Range<f64>,
+    epsilon: f64,
+    function: &SingleVariableFunction,
+) -> Result<(f64, usize), String> {
+    let a = range.start;
+    let b = range.end;
+    let fa = function(a);
+    let fb = function(b);
+    let c = (a + b) / 2.;
+    let fc = function(c);
+    let mut evaluations = 3;
+
+    let whole = (b - a) / 6. * (fa + 4. * fc + fb);
+
+    let integral = adaptive_simpson_step(
+        function,
+        a,
+        b,
+        fa,
+        fb,
+        fc,
+        whole,
+        epsilon,
+        MAX_ADAPTIVE_SIMPSON_DEPTH,
+        &mut evaluations,
+    )?;
+
+    Ok((integral, evaluations))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn adaptive_simpson_step(
+    function: &SingleVariableFunction,
+    a: f64,
+    b: f64,
+    fa: f64,
+    fb: f64,
+    fc: f64,
+    whole: f64,
+    tolerance: f64,
+    depth_remaining: usize,
+    evaluations: &mut usize,
+) -> Result<f64, String> {
+    if depth_remaining == 0 {
+        return Err(format!(
+            "Adaptive Simpson exceeded the maximum recursion depth ({MAX_ADAPTIVE_SIMPSON_DEPTH})"
+        ));
+    }
+
+    let c = (a + b) / 2.;
+    let d = (a + c) / 2.;
+    let e = (c + b) / 2.;
+
+    let fd = function(d);
+    let fe = function(e);
+    *evaluations += 2;
+
+    let left = (c - a) / 6. * (fa + 4. * fd + fc);
+    let right = (b - c) / 6. * (fc + 4. * fe + fb);
+    let refined = left + right;
+
+    if (refined - whole).abs() <= 15. * tolerance {
+        return Ok(refined + (refined - whole) / 15.);
+    }
+
+    let left_result = adaptive_simpson_step(
+        function,
+        a,
+        c,
+        fa,
+        fc,
+        fd,
+        left,
+        tolerance / 2.,
+        depth_remaining - 1,
+        evaluations,
+    )?;
+    let right_result = adaptive_simpson_step(
+        function,
+        c,
+        b,
+        fc,
+        fb,
+        fe,
+        right,
+        tolerance / 2.,
+        depth_remaining - 1,
+        evaluations,
+    )?;
+
+    Ok(left_result + right_result)
+}
+
+/// Romberg integration: builds the full Richardson-extrapolation table,
+/// reusing every previous composite-trapezoid evaluation (each refinement
+/// only samples the new midpoints), and stops once the diagonal stops
+/// improving by more than `epsilon`.
+fn solve_romberg(
+    range: &Range<f64>,
+    epsilon: f64,
+    function: &SingleVariableFunction,
+) -> Result<(f64, usize), String> {
+    let a = range.start;
+    let b = range.end;
+
+    let mut rows: Vec<Vec<f64>> = vec![vec![(b - a) / 2. * (function(a) + function(b))]];
+
+    for i in 1..=MAX_ROMBERG_ROWS {
+        let intervals = 1usize << i;
+        let step = (b - a) / intervals as f64;
+
+        let mut new_midpoints_sum = 0.;
+        let mut point_index = 1;
+        while point_index < intervals {
+            new_midpoints_sum += function(a + point_index as f64 * step);
+            point_index += 2;
+        }
+
+        let trapezoid = rows[i - 1][0] / 2. + step * new_midpoints_sum;
+
+        let mut row = vec![trapezoid];
+        for j in 1..=i {
+            let four_to_j = 4_f64.powi(j as i32);
+            let extrapolated = row[j - 1] + (row[j - 1] - rows[i - 1][j - 1]) / (four_to_j - 1.);
+            row.push(extrapolated);
+        }
+
+        let current_best = row[i];
+        let previous_best = rows[i - 1][i - 1];
+        rows.push(row);
+
+        if (current_best - previous_best).abs() < epsilon {
+            return Ok((current_best, rows.len()));
+        }
+    }
+
+    Err(format!(
+        "Romberg integration did not converge within {MAX_ROMBERG_ROWS} rows"
+    ))
+}
+
+/// Theoretical truncation-error bound for `method`, using a numerically
+/// approximated worst-case high-order derivative over the range rather than
+/// the empirical Runge estimate between two split counts.
+/// Returns `None` for methods that have no closed-form error term implemented.
+/// The caller also discards a non-finite bound, which shows up when the
+/// derivative probe lands on (or just beside) a singular endpoint that was
+/// already routed around by switching to the midpoint rectangle rule.
+fn compute_error_bound(
+    config: &Config,
+    method: ComputationMethod,
+    function: &SingleVariableFunction,
+) -> Option<f64> {
+    let step_length = compute_step_length(config);
+    let length = config.range.len();
+
+    match method {
+        ComputationMethod::Rectangle(RectangleMode::Center) => {
+            let max_second_derivative =
+                max_abs_derivative(&config.range, function, approximate_second_derivative);
+            Some(length / 24. * step_length.powi(2) * max_second_derivative)
+        }
+        ComputationMethod::Rectangle(_) => {
+            let max_first_derivative =
+                max_abs_derivative(&config.range, function, approximate_first_derivative);
+            Some(length / 2. * step_length * max_first_derivative)
+        }
+        ComputationMethod::Trapezoid => {
+            let max_second_derivative =
+                max_abs_derivative(&config.range, function, approximate_second_derivative);
+            Some(length / 12. * step_length.powi(2) * max_second_derivative)
+        }
+        ComputationMethod::Sympthonm => {
+            let max_fourth_derivative =
+                max_abs_derivative(&config.range, function, approximate_fourth_derivative);
+            Some(length / 180. * step_length.powi(4) * max_fourth_derivative)
+        }
+        ComputationMethod::GaussLegendre(_) => None,
+        ComputationMethod::AdaptiveSimpson => {
+            unreachable!("adaptive Simpson bypasses the split-doubling loop entirely")
+        }
+        ComputationMethod::Romberg => {
+            unreachable!("Romberg bypasses the split-doubling loop entirely")
+        }
+    }
+}
+
+/// Samples `approximate` (a finite-difference derivative estimator) over a
+/// grid spanning `range` and returns the largest magnitude found.
+fn max_abs_derivative(
+    range: &Range<f64>,
+    function: &SingleVariableFunction,
+    approximate: impl Fn(&SingleVariableFunction, f64, f64) -> f64,
+) -> f64 {
+    let length = range.len();
+
+    (0..=ERROR_BOUND_SAMPLE_POINTS)
+        .map(|index| range.start + length * index as f64 / ERROR_BOUND_SAMPLE_POINTS as f64)
+        .map(|x| approximate(function, x, ERROR_BOUND_DERIVATIVE_DELTA).abs())
+        .fold(0., f64::max)
+}
+
+fn approximate_first_derivative(function: &SingleVariableFunction, x: f64, delta: f64) -> f64 {
+    (function(x + delta) - function(x - delta)) / (2. * delta)
+}
+
+fn approximate_second_derivative(function: &SingleVariableFunction, x: f64, delta: f64) -> f64 {
+    (function(x - delta) - 2. * function(x) + function(x + delta)) / delta.powi(2)
+}
+
+fn approximate_fourth_derivative(function: &SingleVariableFunction, x: f64, delta: f64) -> f64 {
+    (function(x - 2. * delta) - 4. * function(x - delta) + 6. * function(x) - 4. * function(x + delta)
+        + function(x + 2. * delta))
+        / delta.powi(4)
+}
+
+fn runge_rule<S: Scalar>(half: S, full: S, method: ComputationMethod) -> S {
     let k = match method {
         ComputationMethod::Rectangle(_) | ComputationMethod::Trapezoid => 2,
         ComputationMethod::Sympthonm => 4,
+        ComputationMethod::GaussLegendre(order) => 2 * order as i32,
+        ComputationMethod::AdaptiveSimpson => {
+            unreachable!("adaptive Simpson bypasses the split-doubling loop entirely")
+        }
+        ComputationMethod::Romberg => {
+            unreachable!("Romberg bypasses the split-doubling loop entirely")
+        }
     };
 
-    (half - full) / (2_f64.powi(k) - 1.)
+    ((half - full) / (S::from_f64(2.).powi(k) - S::from_f64(1.))).abs()
 }
@@ -0,0 +1,65 @@
+//! Parses textual (x, y) sample lists for tabulated-data integration.
+//!
+//! Accepts `;`-or-newline-separated `x,y` pairs, e.g. `0,1; 1,4; 2,9`, and
+//! validates that the points are sorted strictly ascending by `x` — the
+//! trapezoid/Simpson-style tabulated solvers both assume this.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `input` into `(x, y)` points, sorted strictly ascending by `x`.
+pub fn parse_points(input: &str) -> Result<Vec<(f64, f64)>, ParseError> {
+    let mut points = Vec::new();
+
+    for pair in input.split([';', '\n']) {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+
+        let mut parts = pair.splitn(2, ',');
+        let x_text = parts
+            .next()
+            .ok_or_else(|| ParseError(format!("malformed pair '{pair}'")))?
+            .trim();
+        let y_text = parts
+            .next()
+            .ok_or_else(|| ParseError(format!("pair '{pair}' is missing a y value")))?
+            .trim();
+
+        let x: f64 = x_text
+            .parse()
+            .map_err(|_| ParseError(format!("invalid x value '{x_text}'")))?;
+        let y: f64 = y_text
+            .parse()
+            .map_err(|_| ParseError(format!("invalid y value '{y_text}'")))?;
+
+        points.push((x, y));
+    }
+
+    if points.len() < 2 {
+        return Err(ParseError("need at least two points to integrate".into()));
+    }
+
+    for window in points.windows(2) {
+        let (x0, _) = window[0];
+        let (x1, _) = window[1];
+        if x1 <= x0 {
+            return Err(ParseError(format!(
+                "points must be strictly sorted by x; {x1} does not come after {x0}"
+            )));
+        }
+    }
+
+    Ok(points)
+}